@@ -0,0 +1,212 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use super::client::{parse_json_response, AIClient, AIError, Role};
+use crate::telegram::client::TelegramClient;
+
+/// Maximum number of tool-call steps before the agent gives up instead of
+/// looping forever
+const MAX_STEPS: usize = 6;
+
+/// Number of recent messages fetched for a `reply` step's conversation
+/// history
+const REPLY_HISTORY_LIMIT: usize = 20;
+
+/// System prompt describing the available tools and the two-shape
+/// response contract: another tool call, or a final answer to stop on
+const AGENT_SYSTEM_PROMPT: &str = r#"You are a Telegram assistant that can chain multiple operations to accomplish a goal.
+On each turn, respond with ONLY one JSON object, no explanation:
+- {"action": "mute", "duration_seconds": <int>} - Mute the current chat (e.g., 3600 for 1 hour)
+- {"action": "unmute"} - Unmute the current chat
+- {"action": "search", "query": "<text>", "from_user": "<optional username>"} - Search messages
+- {"action": "send", "to": "<username>", "text": "<message>"} - Send a message to a user
+- {"action": "reply", "tone": "<casual|formal|technical>"} - Generate a reply draft for the current chat
+- {"action": "finish", "message": "<final answer for the user>"} - Stop and report back
+- {"action": "unknown", "reason": "<explanation>"} - If you can't understand the goal
+
+After each tool call you'll be given its result as "Tool result: ..." or "Tool error: ...". Use it to decide your
+next action, and finish as soon as you have enough information to answer."#;
+
+/// One step the agent can take in response to a turn: either a tool call
+/// to execute against `TelegramClient`, or a final answer to stop on
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+enum AgentStep {
+    Mute { duration_seconds: u32 },
+    Unmute,
+    Search {
+        query: String,
+        from_user: Option<String>,
+    },
+    Send { to: String, text: String },
+    Reply { tone: Option<String> },
+    Finish { message: String },
+    Unknown { reason: String },
+}
+
+/// Gates side-effecting tool calls (`mute`, `unmute`, `send`) behind caller
+/// confirmation before the agent executes them. This matters because the
+/// model's next step can be steered by untrusted content it just read back
+/// from a `search` tool result, so the agent can't treat its own decision
+/// to mute/unmute/send as sufficient authorization on its own.
+#[async_trait]
+pub trait ConfirmGate: Send + Sync {
+    /// Ask whether the action described by `description` (e.g. "send to
+    /// @alice: ...") should go ahead
+    async fn confirm(&self, description: &str) -> bool;
+}
+
+/// Fallback gate that approves every action. Only appropriate when the
+/// caller has already established trust some other way (e.g. scripted
+/// automation with a fixed, trusted goal); interactive use should supply a
+/// gate that actually prompts the user.
+pub struct AlwaysConfirm;
+
+#[async_trait]
+impl ConfirmGate for AlwaysConfirm {
+    async fn confirm(&self, _description: &str) -> bool {
+        true
+    }
+}
+
+/// Runs the agentic function-calling loop: the model emits a tool call,
+/// the agent executes it against `TelegramClient` and feeds the result
+/// back as the next turn, until the model finishes or `MAX_STEPS` is hit.
+pub struct Agent<'a> {
+    ai: &'a AIClient,
+    telegram: &'a TelegramClient,
+    /// Username of the chat currently open in the TUI, used as the
+    /// implicit target for `mute`/`unmute`/`reply`
+    current_chat: Option<String>,
+    /// Approves `mute`/`unmute`/`send` steps before they run
+    confirm: &'a dyn ConfirmGate,
+}
+
+impl<'a> Agent<'a> {
+    pub fn new(
+        ai: &'a AIClient,
+        telegram: &'a TelegramClient,
+        current_chat: Option<String>,
+        confirm: &'a dyn ConfirmGate,
+    ) -> Self {
+        Self {
+            ai,
+            telegram,
+            current_chat,
+            confirm,
+        }
+    }
+
+    /// Run the loop for `goal`, returning the agent's final answer
+    pub async fn run(&self, goal: &str) -> Result<String, AIError> {
+        let mut turns: Vec<(Role, String)> = vec![(Role::User, goal.to_string())];
+
+        for _ in 0..MAX_STEPS {
+            let response = self
+                .ai
+                .complete_conversation(Some(AGENT_SYSTEM_PROMPT), &turns)
+                .await?;
+            turns.push((Role::Model, response.clone()));
+
+            match Self::parse_step(&response)? {
+                AgentStep::Finish { message } => return Ok(message),
+                AgentStep::Unknown { reason } => return Err(AIError::ExecutionError(reason)),
+                step => {
+                    let observation = match self.execute(step).await {
+                        Ok(result) => format!("Tool result: {}", result),
+                        Err(e) => format!("Tool error: {}", e),
+                    };
+                    turns.push((Role::User, observation));
+                }
+            }
+        }
+
+        Err(AIError::ExecutionError(format!(
+            "Did not finish within {} steps",
+            MAX_STEPS
+        )))
+    }
+
+    fn parse_step(response: &str) -> Result<AgentStep, AIError> {
+        parse_json_response(response, "agent step")
+    }
+
+    async fn execute(&self, step: AgentStep) -> Result<String, AIError> {
+        match step {
+            AgentStep::Mute { duration_seconds } => {
+                let chat = self.require_current_chat()?;
+                self.require_confirmation(&format!("mute {} for {}s", chat, duration_seconds))
+                    .await?;
+                self.telegram
+                    .mute_chat(chat, duration_seconds)
+                    .await
+                    .map_err(|e| AIError::ExecutionError(e.to_string()))?;
+                Ok(format!("Muted {} for {}s", chat, duration_seconds))
+            }
+            AgentStep::Unmute => {
+                let chat = self.require_current_chat()?;
+                self.require_confirmation(&format!("unmute {}", chat)).await?;
+                self.telegram
+                    .unmute_chat(chat)
+                    .await
+                    .map_err(|e| AIError::ExecutionError(e.to_string()))?;
+                Ok(format!("Unmuted {}", chat))
+            }
+            AgentStep::Search { query, from_user } => {
+                let matches = self
+                    .telegram
+                    .search_messages(&query, from_user.as_deref())
+                    .await
+                    .map_err(|e| AIError::ExecutionError(e.to_string()))?;
+                Ok(format!("{} match(es): {}", matches.len(), matches.join(" | ")))
+            }
+            AgentStep::Send { to, text } => {
+                self.require_confirmation(&format!("send to {}: {}", to, text))
+                    .await?;
+                self.telegram
+                    .send_message(&to, &text)
+                    .await
+                    .map_err(|e| AIError::ExecutionError(e.to_string()))?;
+                Ok(format!("Sent to {}", to))
+            }
+            AgentStep::Reply { tone } => {
+                let chat = self.require_current_chat()?;
+                let history = self
+                    .telegram
+                    .chat_history(chat, REPLY_HISTORY_LIMIT)
+                    .await
+                    .map_err(|e| AIError::ExecutionError(e.to_string()))?;
+                let turns: Vec<(Role, String)> = history
+                    .into_iter()
+                    .map(|m| {
+                        let role = if m.from_me { Role::Model } else { Role::User };
+                        (role, m.text)
+                    })
+                    .collect();
+                self.ai.generate_reply(&turns, tone.as_deref()).await
+            }
+            AgentStep::Finish { .. } | AgentStep::Unknown { .. } => {
+                unreachable!("Finish/Unknown are handled by the caller before execute()")
+            }
+        }
+    }
+
+    fn require_current_chat(&self) -> Result<&str, AIError> {
+        self.current_chat.as_deref().ok_or_else(|| {
+            AIError::ExecutionError("no chat is currently open".to_string())
+        })
+    }
+
+    /// Ask `self.confirm` before a side-effecting step runs, failing the
+    /// step (not the whole agent run) if the caller declines
+    async fn require_confirmation(&self, description: &str) -> Result<(), AIError> {
+        if self.confirm.confirm(description).await {
+            Ok(())
+        } else {
+            Err(AIError::ExecutionError(format!(
+                "action declined by user: {}",
+                description
+            )))
+        }
+    }
+}
@@ -0,0 +1,869 @@
+use async_trait::async_trait;
+use futures::stream::{self, BoxStream, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use super::client::{AIError, Role};
+use super::config::AIConfig;
+
+/// A pluggable LLM backend capable of completing a single-turn prompt or a
+/// multi-turn conversation.
+///
+/// Implementations translate the neutral `(system, user)` pair (or the
+/// neutral `(Role, String)` turn list) into whatever wire format their
+/// provider expects, and translate the response (or error) back into
+/// `AIError`. `AIClient` holds one of these behind a trait object, chosen
+/// at construction time from `AIConfig::provider`.
+#[async_trait]
+pub trait Backend: Send + Sync {
+    async fn complete_with_system(
+        &self,
+        system: Option<&str>,
+        user: &str,
+    ) -> Result<String, AIError>;
+
+    /// Complete a full conversation of alternating user/model turns. The
+    /// default implementation flattens the turns into a single user
+    /// message for backends that don't yet have a dedicated conversation
+    /// implementation.
+    async fn complete_conversation(
+        &self,
+        system: Option<&str>,
+        turns: &[(Role, String)],
+    ) -> Result<String, AIError> {
+        let flattened = turns
+            .iter()
+            .map(|(role, text)| format!("{:?}: {}", role, text))
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.complete_with_system(system, &flattened).await
+    }
+
+    /// Stream a single-turn completion as incremental text deltas. The
+    /// default implementation falls back to a single non-streaming call
+    /// and yields the whole response as one chunk, for backends that
+    /// don't have a dedicated streaming endpoint.
+    async fn complete_stream(
+        &self,
+        system: Option<&str>,
+        user: &str,
+    ) -> Result<BoxStream<'static, Result<String, AIError>>, AIError> {
+        let text = self.complete_with_system(system, user).await?;
+        Ok(stream::once(async { Ok(text) }).boxed())
+    }
+}
+
+/// Turn a 429 response into `AIError::RateLimited` populated with the
+/// actual wait time: the standard `Retry-After` header if present,
+/// otherwise Gemini's structured `RetryInfo` detail in the error body,
+/// otherwise a conservative default.
+async fn rate_limited_error(response: reqwest::Response) -> AIError {
+    if let Some(secs) = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+    {
+        return AIError::RateLimited(secs);
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    AIError::RateLimited(parse_gemini_retry_delay(&body).unwrap_or(60))
+}
+
+/// Gemini's 429 body carries a `RetryInfo` detail shaped like
+/// `{"error": {"details": [{"retryDelay": "19s"}]}}`
+fn parse_gemini_retry_delay(body: &str) -> Option<u64> {
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+    value
+        .get("error")?
+        .get("details")?
+        .as_array()?
+        .iter()
+        .find_map(|d| d.get("retryDelay")?.as_str())
+        .and_then(|s| s.trim_end_matches('s').parse::<u64>().ok())
+}
+
+/// Find the start of the first `\n\n` event separator in a raw SSE byte
+/// buffer, if a complete event has arrived yet
+fn find_sse_boundary(buf: &[u8]) -> Option<usize> {
+    buf.windows(2).position(|w| w == b"\n\n")
+}
+
+/// Build the backend selected by `AIConfig::provider`, defaulting to Gemini
+/// for unrecognized values so existing configs keep working unchanged.
+pub fn build(config: &AIConfig) -> Box<dyn Backend> {
+    match config.provider.as_str() {
+        "openai" => Box::new(OpenAIBackend::new(config)),
+        "ollama" => Box::new(OllamaBackend::new(config)),
+        "anthropic" => Box::new(AnthropicBackend::new(config)),
+        _ => Box::new(GeminiBackend::new(config)),
+    }
+}
+
+/// Google Gemini request format
+#[derive(Debug, Serialize)]
+struct GeminiRequest {
+    contents: Vec<GeminiContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<GeminiContent>,
+    #[serde(rename = "generationConfig", skip_serializing_if = "Option::is_none")]
+    generation_config: Option<GenerationConfig>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct GeminiContent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role: Option<String>,
+    parts: Vec<GeminiPart>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct GeminiPart {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+struct GenerationConfig {
+    temperature: f32,
+    #[serde(rename = "maxOutputTokens")]
+    max_output_tokens: u32,
+}
+
+/// Google Gemini response format
+#[derive(Debug, Deserialize)]
+struct GeminiResponse {
+    candidates: Option<Vec<GeminiCandidate>>,
+    error: Option<GeminiError>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct GeminiCandidate {
+    content: GeminiContent,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiError {
+    message: String,
+}
+
+/// Backend for Google's Gemini `generateContent` API
+pub struct GeminiBackend {
+    http_client: reqwest::Client,
+    base_url: String,
+    model: String,
+    api_key: String,
+}
+
+impl GeminiBackend {
+    pub fn new(config: &AIConfig) -> Self {
+        Self {
+            http_client: reqwest::Client::new(),
+            base_url: config.base_url.clone(),
+            model: config.model.clone(),
+            api_key: config.api_key.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl Backend for GeminiBackend {
+    async fn complete_with_system(
+        &self,
+        system: Option<&str>,
+        user: &str,
+    ) -> Result<String, AIError> {
+        let request = GeminiRequest {
+            contents: vec![GeminiContent {
+                role: Some("user".to_string()),
+                parts: vec![GeminiPart {
+                    text: user.to_string(),
+                }],
+            }],
+            system_instruction: system.map(|s| GeminiContent {
+                role: None,
+                parts: vec![GeminiPart {
+                    text: s.to_string(),
+                }],
+            }),
+            generation_config: Some(GenerationConfig {
+                temperature: 0.7,
+                max_output_tokens: 2048,
+            }),
+        };
+
+        // Gemini API URL format: {base_url}/models/{model}:generateContent?key={api_key}
+        let url = format!(
+            "{}/models/{}:generateContent?key={}",
+            self.base_url, self.model, self.api_key
+        );
+
+        let response = self
+            .http_client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| AIError::NetworkError(e.to_string()))?;
+
+        if response.status() == 429 {
+            return Err(rate_limited_error(response).await);
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AIError::ApiError(format!("{}: {}", status, body)));
+        }
+
+        let gemini_response: GeminiResponse = response
+            .json()
+            .await
+            .map_err(|e| AIError::ParseError(e.to_string()))?;
+
+        // Check for API error in response
+        if let Some(error) = gemini_response.error {
+            return Err(AIError::ApiError(error.message));
+        }
+
+        // Extract text from response
+        gemini_response
+            .candidates
+            .and_then(|c| c.first().cloned())
+            .and_then(|c| c.content.parts.first().cloned())
+            .map(|p| p.text)
+            .ok_or_else(|| AIError::ParseError("No response from AI".to_string()))
+    }
+
+    async fn complete_conversation(
+        &self,
+        system: Option<&str>,
+        turns: &[(Role, String)],
+    ) -> Result<String, AIError> {
+        let contents = turns
+            .iter()
+            .map(|(role, text)| GeminiContent {
+                role: Some(
+                    match role {
+                        Role::User => "user",
+                        Role::Model => "model",
+                    }
+                    .to_string(),
+                ),
+                parts: vec![GeminiPart { text: text.clone() }],
+            })
+            .collect();
+
+        let request = GeminiRequest {
+            contents,
+            system_instruction: system.map(|s| GeminiContent {
+                role: None,
+                parts: vec![GeminiPart {
+                    text: s.to_string(),
+                }],
+            }),
+            generation_config: Some(GenerationConfig {
+                temperature: 0.7,
+                max_output_tokens: 2048,
+            }),
+        };
+
+        let url = format!(
+            "{}/models/{}:generateContent?key={}",
+            self.base_url, self.model, self.api_key
+        );
+
+        let response = self
+            .http_client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| AIError::NetworkError(e.to_string()))?;
+
+        if response.status() == 429 {
+            return Err(rate_limited_error(response).await);
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AIError::ApiError(format!("{}: {}", status, body)));
+        }
+
+        let gemini_response: GeminiResponse = response
+            .json()
+            .await
+            .map_err(|e| AIError::ParseError(e.to_string()))?;
+
+        if let Some(error) = gemini_response.error {
+            return Err(AIError::ApiError(error.message));
+        }
+
+        gemini_response
+            .candidates
+            .and_then(|c| c.first().cloned())
+            .and_then(|c| c.content.parts.first().cloned())
+            .map(|p| p.text)
+            .ok_or_else(|| AIError::ParseError("No response from AI".to_string()))
+    }
+
+    async fn complete_stream(
+        &self,
+        system: Option<&str>,
+        user: &str,
+    ) -> Result<BoxStream<'static, Result<String, AIError>>, AIError> {
+        let request = GeminiRequest {
+            contents: vec![GeminiContent {
+                role: Some("user".to_string()),
+                parts: vec![GeminiPart {
+                    text: user.to_string(),
+                }],
+            }],
+            system_instruction: system.map(|s| GeminiContent {
+                role: None,
+                parts: vec![GeminiPart {
+                    text: s.to_string(),
+                }],
+            }),
+            generation_config: Some(GenerationConfig {
+                temperature: 0.7,
+                max_output_tokens: 2048,
+            }),
+        };
+
+        // Gemini's streaming endpoint emits Server-Sent Events, each a
+        // `data: {...}\n\n` frame carrying an incremental GeminiResponse.
+        let url = format!(
+            "{}/models/{}:streamGenerateContent?alt=sse&key={}",
+            self.base_url, self.model, self.api_key
+        );
+
+        let response = self
+            .http_client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| AIError::NetworkError(e.to_string()))?;
+
+        if response.status() == 429 {
+            return Err(rate_limited_error(response).await);
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AIError::ApiError(format!("{}: {}", status, body)));
+        }
+
+        let byte_stream = response.bytes_stream();
+
+        let text_stream = async_stream::try_stream! {
+            futures::pin_mut!(byte_stream);
+            // Buffered as raw bytes, not `String`: a chunk boundary can
+            // split a multi-byte UTF-8 character, so we only decode once a
+            // complete `\n\n`-delimited event has been reassembled.
+            let mut buf: Vec<u8> = Vec::new();
+
+            while let Some(chunk) = byte_stream.next().await {
+                let chunk = chunk.map_err(|e| AIError::NetworkError(e.to_string()))?;
+                buf.extend_from_slice(&chunk);
+
+                while let Some(pos) = find_sse_boundary(&buf) {
+                    let event = String::from_utf8_lossy(&buf[..pos]).into_owned();
+                    buf.drain(..pos + 2);
+
+                    for line in event.lines() {
+                        let Some(data) = line.strip_prefix("data: ") else {
+                            continue;
+                        };
+                        let parsed: GeminiResponse = serde_json::from_str(data)
+                            .map_err(|e| AIError::ParseError(e.to_string()))?;
+
+                        if let Some(error) = parsed.error {
+                            Err(AIError::ApiError(error.message))?;
+                        }
+
+                        if let Some(text) = parsed
+                            .candidates
+                            .and_then(|c| c.into_iter().next())
+                            .and_then(|c| c.content.parts.into_iter().next())
+                            .map(|p| p.text)
+                        {
+                            yield text;
+                        }
+                    }
+                }
+            }
+        };
+
+        Ok(text_stream.boxed())
+    }
+}
+
+/// OpenAI-compatible `/chat/completions` request format (also served by
+/// llama.cpp, vLLM, and most self-hosted gateways)
+#[derive(Debug, Serialize)]
+struct ChatCompletionsRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    temperature: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct ChatMessage {
+    role: String,
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionsResponse {
+    choices: Option<Vec<ChatCompletionsChoice>>,
+    error: Option<ChatCompletionsError>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct ChatCompletionsChoice {
+    message: ChatMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionsError {
+    message: String,
+}
+
+/// Backend for OpenAI and OpenAI-compatible `/chat/completions` endpoints
+pub struct OpenAIBackend {
+    http_client: reqwest::Client,
+    base_url: String,
+    model: String,
+    api_key: String,
+}
+
+impl OpenAIBackend {
+    pub fn new(config: &AIConfig) -> Self {
+        Self {
+            http_client: reqwest::Client::new(),
+            base_url: config.base_url.clone(),
+            model: config.model.clone(),
+            api_key: config.api_key.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl Backend for OpenAIBackend {
+    async fn complete_with_system(
+        &self,
+        system: Option<&str>,
+        user: &str,
+    ) -> Result<String, AIError> {
+        let mut messages = Vec::new();
+        if let Some(system) = system {
+            messages.push(ChatMessage {
+                role: "system".to_string(),
+                content: system.to_string(),
+            });
+        }
+        messages.push(ChatMessage {
+            role: "user".to_string(),
+            content: user.to_string(),
+        });
+
+        let request = ChatCompletionsRequest {
+            model: self.model.clone(),
+            messages,
+            temperature: 0.7,
+        };
+
+        let url = format!("{}/chat/completions", self.base_url);
+
+        let response = self
+            .http_client
+            .post(&url)
+            .bearer_auth(&self.api_key)
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| AIError::NetworkError(e.to_string()))?;
+
+        if response.status() == 429 {
+            return Err(rate_limited_error(response).await);
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AIError::ApiError(format!("{}: {}", status, body)));
+        }
+
+        let chat_response: ChatCompletionsResponse = response
+            .json()
+            .await
+            .map_err(|e| AIError::ParseError(e.to_string()))?;
+
+        if let Some(error) = chat_response.error {
+            return Err(AIError::ApiError(error.message));
+        }
+
+        chat_response
+            .choices
+            .and_then(|c| c.into_iter().next())
+            .map(|c| c.message.content)
+            .ok_or_else(|| AIError::ParseError("No response from AI".to_string()))
+    }
+
+    async fn complete_conversation(
+        &self,
+        system: Option<&str>,
+        turns: &[(Role, String)],
+    ) -> Result<String, AIError> {
+        let mut messages = Vec::new();
+        if let Some(system) = system {
+            messages.push(ChatMessage {
+                role: "system".to_string(),
+                content: system.to_string(),
+            });
+        }
+        messages.extend(turns.iter().map(|(role, text)| ChatMessage {
+            role: match role {
+                Role::User => "user",
+                Role::Model => "assistant",
+            }
+            .to_string(),
+            content: text.clone(),
+        }));
+
+        let request = ChatCompletionsRequest {
+            model: self.model.clone(),
+            messages,
+            temperature: 0.7,
+        };
+
+        let url = format!("{}/chat/completions", self.base_url);
+
+        let response = self
+            .http_client
+            .post(&url)
+            .bearer_auth(&self.api_key)
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| AIError::NetworkError(e.to_string()))?;
+
+        if response.status() == 429 {
+            return Err(rate_limited_error(response).await);
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AIError::ApiError(format!("{}: {}", status, body)));
+        }
+
+        let chat_response: ChatCompletionsResponse = response
+            .json()
+            .await
+            .map_err(|e| AIError::ParseError(e.to_string()))?;
+
+        if let Some(error) = chat_response.error {
+            return Err(AIError::ApiError(error.message));
+        }
+
+        chat_response
+            .choices
+            .and_then(|c| c.into_iter().next())
+            .map(|c| c.message.content)
+            .ok_or_else(|| AIError::ParseError("No response from AI".to_string()))
+    }
+}
+
+/// Anthropic `/v1/messages` request format
+#[derive(Debug, Serialize)]
+struct AnthropicRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicResponse {
+    content: Option<Vec<AnthropicContentBlock>>,
+    error: Option<AnthropicError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicContentBlock {
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicError {
+    message: String,
+}
+
+/// Backend for Anthropic's Messages API. Unlike the OpenAI/Ollama chat
+/// formats, the system prompt is a top-level field rather than a message
+/// with role `"system"`.
+pub struct AnthropicBackend {
+    http_client: reqwest::Client,
+    base_url: String,
+    model: String,
+    api_key: String,
+}
+
+impl AnthropicBackend {
+    pub fn new(config: &AIConfig) -> Self {
+        Self {
+            http_client: reqwest::Client::new(),
+            base_url: config.base_url.clone(),
+            model: config.model.clone(),
+            api_key: config.api_key.clone(),
+        }
+    }
+
+    async fn send(&self, request: &AnthropicRequest) -> Result<String, AIError> {
+        let url = format!("{}/v1/messages", self.base_url);
+
+        let response = self
+            .http_client
+            .post(&url)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .header("Content-Type", "application/json")
+            .json(request)
+            .send()
+            .await
+            .map_err(|e| AIError::NetworkError(e.to_string()))?;
+
+        if response.status() == 429 {
+            return Err(rate_limited_error(response).await);
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AIError::ApiError(format!("{}: {}", status, body)));
+        }
+
+        let parsed: AnthropicResponse = response
+            .json()
+            .await
+            .map_err(|e| AIError::ParseError(e.to_string()))?;
+
+        if let Some(error) = parsed.error {
+            return Err(AIError::ApiError(error.message));
+        }
+
+        parsed
+            .content
+            .and_then(|c| c.into_iter().next())
+            .map(|b| b.text)
+            .ok_or_else(|| AIError::ParseError("No response from AI".to_string()))
+    }
+}
+
+#[async_trait]
+impl Backend for AnthropicBackend {
+    async fn complete_with_system(
+        &self,
+        system: Option<&str>,
+        user: &str,
+    ) -> Result<String, AIError> {
+        let request = AnthropicRequest {
+            model: self.model.clone(),
+            messages: vec![ChatMessage {
+                role: "user".to_string(),
+                content: user.to_string(),
+            }],
+            max_tokens: 2048,
+            system: system.map(|s| s.to_string()),
+        };
+
+        self.send(&request).await
+    }
+
+    async fn complete_conversation(
+        &self,
+        system: Option<&str>,
+        turns: &[(Role, String)],
+    ) -> Result<String, AIError> {
+        let messages = turns
+            .iter()
+            .map(|(role, text)| ChatMessage {
+                role: match role {
+                    Role::User => "user",
+                    Role::Model => "assistant",
+                }
+                .to_string(),
+                content: text.clone(),
+            })
+            .collect();
+
+        let request = AnthropicRequest {
+            model: self.model.clone(),
+            messages,
+            max_tokens: 2048,
+            system: system.map(|s| s.to_string()),
+        };
+
+        self.send(&request).await
+    }
+}
+
+/// Ollama `/api/chat` request format
+#[derive(Debug, Serialize)]
+struct OllamaRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    stream: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaResponse {
+    message: Option<ChatMessage>,
+    error: Option<String>,
+}
+
+/// Backend for a local or remote Ollama server
+pub struct OllamaBackend {
+    http_client: reqwest::Client,
+    base_url: String,
+    model: String,
+}
+
+impl OllamaBackend {
+    pub fn new(config: &AIConfig) -> Self {
+        Self {
+            http_client: reqwest::Client::new(),
+            base_url: config.base_url.clone(),
+            model: config.model.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl Backend for OllamaBackend {
+    async fn complete_with_system(
+        &self,
+        system: Option<&str>,
+        user: &str,
+    ) -> Result<String, AIError> {
+        let mut messages = Vec::new();
+        if let Some(system) = system {
+            messages.push(ChatMessage {
+                role: "system".to_string(),
+                content: system.to_string(),
+            });
+        }
+        messages.push(ChatMessage {
+            role: "user".to_string(),
+            content: user.to_string(),
+        });
+
+        let request = OllamaRequest {
+            model: self.model.clone(),
+            messages,
+            stream: false,
+        };
+
+        // Ollama has no API key; it's expected to run on localhost or a
+        // trusted network.
+        let url = format!("{}/api/chat", self.base_url);
+
+        let response = self
+            .http_client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| AIError::NetworkError(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AIError::ApiError(format!("{}: {}", status, body)));
+        }
+
+        let ollama_response: OllamaResponse = response
+            .json()
+            .await
+            .map_err(|e| AIError::ParseError(e.to_string()))?;
+
+        if let Some(error) = ollama_response.error {
+            return Err(AIError::ApiError(error));
+        }
+
+        ollama_response
+            .message
+            .map(|m| m.content)
+            .ok_or_else(|| AIError::ParseError("No response from AI".to_string()))
+    }
+
+    async fn complete_conversation(
+        &self,
+        system: Option<&str>,
+        turns: &[(Role, String)],
+    ) -> Result<String, AIError> {
+        let mut messages = Vec::new();
+        if let Some(system) = system {
+            messages.push(ChatMessage {
+                role: "system".to_string(),
+                content: system.to_string(),
+            });
+        }
+        messages.extend(turns.iter().map(|(role, text)| ChatMessage {
+            role: match role {
+                Role::User => "user",
+                Role::Model => "assistant",
+            }
+            .to_string(),
+            content: text.clone(),
+        }));
+
+        let request = OllamaRequest {
+            model: self.model.clone(),
+            messages,
+            stream: false,
+        };
+
+        let url = format!("{}/api/chat", self.base_url);
+
+        let response = self
+            .http_client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| AIError::NetworkError(e.to_string()))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AIError::ApiError(format!("{}: {}", status, body)));
+        }
+
+        let ollama_response: OllamaResponse = response
+            .json()
+            .await
+            .map_err(|e| AIError::ParseError(e.to_string()))?;
+
+        if let Some(error) = ollama_response.error {
+            return Err(AIError::ApiError(error));
+        }
+
+        ollama_response
+            .message
+            .map(|m| m.content)
+            .ok_or_else(|| AIError::ParseError("No response from AI".to_string()))
+    }
+}
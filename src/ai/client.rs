@@ -1,8 +1,20 @@
-use serde::{Deserialize, Serialize};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use serde::Deserialize;
 use std::error::Error;
 use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use super::backend::{self, Backend};
 use super::config::AIConfig;
+use super::logger::Logger;
+use super::rate_limiter::RateLimiter;
+
+/// Number of attempts a transient network error gets before giving up, and
+/// the base delay the exponential backoff starts from
+const MAX_NETWORK_RETRIES: u32 = 3;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(200);
 
 /// AI API error types
 #[derive(Debug)]
@@ -12,6 +24,7 @@ pub enum AIError {
     ApiError(String),
     ParseError(String),
     RateLimited(u64), // seconds to wait
+    ExecutionError(String),
 }
 
 impl fmt::Display for AIError {
@@ -24,73 +37,52 @@ impl fmt::Display for AIError {
             AIError::ApiError(e) => write!(f, "API error: {}", e),
             AIError::ParseError(e) => write!(f, "Parse error: {}", e),
             AIError::RateLimited(secs) => write!(f, "Rate limited. Try again in {}s", secs),
+            AIError::ExecutionError(e) => write!(f, "Tool execution failed: {}", e),
         }
     }
 }
 
 impl Error for AIError {}
 
-/// Google Gemini request format
-#[derive(Debug, Serialize)]
-struct GeminiRequest {
-    contents: Vec<GeminiContent>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    system_instruction: Option<GeminiContent>,
-    #[serde(rename = "generationConfig", skip_serializing_if = "Option::is_none")]
-    generation_config: Option<GenerationConfig>,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-struct GeminiContent {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    role: Option<String>,
-    parts: Vec<GeminiPart>,
-}
-
-#[derive(Debug, Serialize, Deserialize, Clone)]
-struct GeminiPart {
-    text: String,
-}
-
-#[derive(Debug, Serialize)]
-struct GenerationConfig {
-    temperature: f32,
-    #[serde(rename = "maxOutputTokens")]
-    max_output_tokens: u32,
-}
-
-/// Google Gemini response format
-#[derive(Debug, Deserialize)]
-struct GeminiResponse {
-    candidates: Option<Vec<GeminiCandidate>>,
-    error: Option<GeminiError>,
-}
-
-#[derive(Debug, Deserialize, Clone)]
-struct GeminiCandidate {
-    content: GeminiContent,
-}
-
-#[derive(Debug, Deserialize)]
-struct GeminiError {
-    message: String,
+/// Who spoke a given turn in a conversation passed to `complete_conversation`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    User,
+    Model,
 }
 
-/// AI Client for Google Gemini API
+/// AI Client; delegates the actual request/response shape to a pluggable
+/// `Backend` (Gemini, OpenAI-compatible, Ollama, ...) selected from
+/// `AIConfig::provider`.
 pub struct AIClient {
-    http_client: reqwest::Client,
+    backend: Box<dyn Backend>,
     config: AIConfig,
+    rate_limiter: RateLimiter,
+    logger: Option<Arc<Logger>>,
 }
 
 impl AIClient {
-    /// Create a new AI client
+    /// Create a new AI client. Request/response logging is enabled
+    /// automatically when `AIConfig::resolve_log_path` resolves to a path;
+    /// use `with_logger` to override it.
     pub fn new(config: AIConfig) -> Self {
+        let backend = backend::build(&config);
+        let rate_limiter = RateLimiter::new(config.max_requests_per_second);
+        let logger = config.resolve_log_path().map(|path| Arc::new(Logger::new(path)));
         Self {
-            http_client: reqwest::Client::new(),
+            backend,
             config,
+            rate_limiter,
+            logger,
         }
     }
 
+    /// Attach (or replace) the request/response logger
+    pub fn with_logger(mut self, logger: Logger) -> Self {
+        self.logger = Some(Arc::new(logger));
+        self
+    }
+
     /// Check if the client is ready to make requests
     pub fn is_ready(&self) -> bool {
         self.config.is_ready()
@@ -111,67 +103,153 @@ impl AIClient {
             return Err(AIError::NotConfigured);
         }
 
-        let request = GeminiRequest {
-            contents: vec![GeminiContent {
-                role: Some("user".to_string()),
-                parts: vec![GeminiPart {
-                    text: user.to_string(),
-                }],
-            }],
-            system_instruction: system.map(|s| GeminiContent {
-                role: None,
-                parts: vec![GeminiPart {
-                    text: s.to_string(),
-                }],
-            }),
-            generation_config: Some(GenerationConfig {
-                temperature: 0.7,
-                max_output_tokens: 2048,
-            }),
+        let started = Instant::now();
+        let mut attempt = 0;
+        let result = loop {
+            self.rate_limiter.acquire().await;
+            match self.backend.complete_with_system(system, user).await {
+                Err(AIError::NetworkError(_)) if attempt + 1 < MAX_NETWORK_RETRIES => {
+                    attempt += 1;
+                    tokio::time::sleep(RETRY_BASE_DELAY * 2u32.pow(attempt - 1)).await;
+                }
+                result => break result,
+            }
         };
 
-        // Gemini API URL format: {base_url}/models/{model}:generateContent?key={api_key}
-        let url = format!(
-            "{}/models/{}:generateContent?key={}",
-            self.config.base_url, self.config.model, self.config.api_key
-        );
+        self.log_completion(user, started.elapsed(), &result);
+        result
+    }
 
-        let response = self
-            .http_client
-            .post(&url)
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| AIError::NetworkError(e.to_string()))?;
-
-        if response.status() == 429 {
-            return Err(AIError::RateLimited(60));
+    /// Complete a full conversation of alternating user/model turns,
+    /// letting the model see who said what instead of a single flattened
+    /// blob
+    pub async fn complete_conversation(
+        &self,
+        system: Option<&str>,
+        turns: &[(Role, String)],
+    ) -> Result<String, AIError> {
+        if !self.config.is_ready() {
+            return Err(AIError::NotConfigured);
         }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(AIError::ApiError(format!("{}: {}", status, body)));
-        }
+        let started = Instant::now();
+        let mut attempt = 0;
+        let result = loop {
+            self.rate_limiter.acquire().await;
+            match self.backend.complete_conversation(system, turns).await {
+                Err(AIError::NetworkError(_)) if attempt + 1 < MAX_NETWORK_RETRIES => {
+                    attempt += 1;
+                    tokio::time::sleep(RETRY_BASE_DELAY * 2u32.pow(attempt - 1)).await;
+                }
+                result => break result,
+            }
+        };
 
-        let gemini_response: GeminiResponse = response
-            .json()
-            .await
-            .map_err(|e| AIError::ParseError(e.to_string()))?;
+        let flattened_prompt = turns
+            .iter()
+            .map(|(_, text)| text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.log_completion(&flattened_prompt, started.elapsed(), &result);
+        result
+    }
 
-        // Check for API error in response
-        if let Some(error) = gemini_response.error {
-            return Err(AIError::ApiError(error.message));
+    /// Stream a single-turn completion as incremental text deltas, so a
+    /// reply or `code_assist` answer can be rendered progressively instead
+    /// of blocking until the full response returns. Rate-limited and
+    /// retried the same way as `complete_with_system`; note that only
+    /// *establishing* the stream is retried; a network error partway
+    /// through an already-started stream is surfaced to the caller as an
+    /// item on the stream instead, since any already-emitted deltas can't
+    /// be un-rendered.
+    pub async fn complete_stream(
+        &self,
+        system: Option<&str>,
+        user: &str,
+    ) -> Result<BoxStream<'static, Result<String, AIError>>, AIError> {
+        if !self.config.is_ready() {
+            return Err(AIError::NotConfigured);
         }
 
-        // Extract text from response
-        gemini_response
-            .candidates
-            .and_then(|c| c.first().cloned())
-            .and_then(|c| c.content.parts.first().cloned())
-            .map(|p| p.text)
-            .ok_or_else(|| AIError::ParseError("No response from AI".to_string()))
+        let started = Instant::now();
+        let mut attempt = 0;
+        let inner = loop {
+            self.rate_limiter.acquire().await;
+            match self.backend.complete_stream(system, user).await {
+                Err(AIError::NetworkError(_)) if attempt + 1 < MAX_NETWORK_RETRIES => {
+                    attempt += 1;
+                    tokio::time::sleep(RETRY_BASE_DELAY * 2u32.pow(attempt - 1)).await;
+                }
+                result => break result,
+            }
+        }?;
+
+        Ok(self.log_stream(inner, user.to_string(), started))
+    }
+
+    /// Wrap a raw backend stream so the accumulated response text (or the
+    /// first error) gets logged once the stream ends, the same as the
+    /// non-streaming completion methods. Deltas pass through untouched.
+    fn log_stream(
+        &self,
+        mut inner: BoxStream<'static, Result<String, AIError>>,
+        prompt: String,
+        started: Instant,
+    ) -> BoxStream<'static, Result<String, AIError>> {
+        let Some(logger) = self.logger.clone() else {
+            return inner;
+        };
+
+        let provider = self.config.provider.clone();
+        let model = self.config.model.clone();
+        let base_url = self.config.base_url.clone();
+
+        Box::pin(async_stream::stream! {
+            let mut accumulated = String::new();
+            let mut failure: Option<String> = None;
+
+            while let Some(item) = inner.next().await {
+                match &item {
+                    Ok(delta) => accumulated.push_str(delta),
+                    Err(e) => failure = Some(e.to_string()),
+                }
+                let is_err = item.is_err();
+                yield item;
+                if is_err {
+                    break;
+                }
+            }
+
+            let response = match &failure {
+                Some(text) => Err(text.as_str()),
+                None => Ok(accumulated.as_str()),
+            };
+            logger.log_completion(&provider, &model, &base_url, started.elapsed(), &prompt, response);
+        })
+    }
+
+    /// Forward a completed (or failed) request to the logger, if one is
+    /// configured
+    fn log_completion(&self, prompt: &str, latency: Duration, result: &Result<String, AIError>) {
+        let Some(logger) = &self.logger else {
+            return;
+        };
+
+        let error_text = result.as_ref().err().map(|e| e.to_string());
+        let response = match (result, &error_text) {
+            (Ok(text), _) => Ok(text.as_str()),
+            (Err(_), Some(text)) => Err(text.as_str()),
+            (Err(_), None) => unreachable!(),
+        };
+
+        logger.log_completion(
+            &self.config.provider,
+            &self.config.model,
+            &self.config.base_url,
+            latency,
+            prompt,
+            response,
+        );
     }
 
     /// Parse a command from natural language
@@ -188,29 +266,15 @@ Available actions:
 Respond with ONLY valid JSON, no explanation."#;
 
         let response = self.complete_with_system(Some(system), input).await?;
-
-        // Try to parse the JSON response
-        let trimmed = response.trim();
-        // Handle markdown code blocks
-        let json_str = if trimmed.starts_with("```") {
-            trimmed
-                .trim_start_matches("```json")
-                .trim_start_matches("```")
-                .trim_end_matches("```")
-                .trim()
-        } else {
-            trimmed
-        };
-
-        serde_json::from_str(json_str).map_err(|e| {
-            AIError::ParseError(format!("Invalid JSON: {} - Response: {}", e, response))
-        })
+        parse_json_response(&response, "JSON")
     }
 
-    /// Generate a reply draft based on chat context
+    /// Generate a reply draft from the open chat's history, fed in as real
+    /// alternating turns (`Role::User` for the other person, `Role::Model`
+    /// for our own prior messages) so the model sees who said what
     pub async fn generate_reply(
         &self,
-        context: &str,
+        turns: &[(Role, String)],
         tone: Option<&str>,
     ) -> Result<String, AIError> {
         let tone_instruction = match tone {
@@ -229,23 +293,46 @@ Respond with ONLY the reply text, no quotes or explanation."#,
             tone_instruction
         );
 
-        self.complete_with_system(
-            Some(&system),
-            &format!("Chat history:\n{}\n\nDraft a reply:", context),
-        )
-        .await
+        self.complete_conversation(Some(&system), turns).await
     }
 
     /// Generate code or explain programming concepts
     pub async fn code_assist(&self, query: &str) -> Result<String, AIError> {
-        let system = r#"You are a coding assistant integrated into a terminal app.
+        self.complete_with_system(Some(CODE_ASSIST_SYSTEM), query).await
+    }
+}
+
+/// System prompt shared by `code_assist` and the `:code` streaming path in
+/// `ui::streaming`, so both give the model the same instructions
+pub const CODE_ASSIST_SYSTEM: &str = r#"You are a coding assistant integrated into a terminal app.
 - Respond concisely
 - Use markdown code blocks with language tags
 - For debugging, explain the issue clearly
 - Provide working, practical code examples"#;
 
-        self.complete_with_system(Some(system), query).await
-    }
+/// Parse a model response into `T`, stripping a ```` ```json ... ``` ````
+/// (or bare ` ``` `) fence some models wrap their JSON output in first.
+/// `what` labels the parse error (e.g. "JSON", "agent step") so callers with
+/// different expected shapes get distinguishable error messages. Shared by
+/// `AIClient::parse_command` and `Agent::parse_step`.
+pub(crate) fn parse_json_response<T: serde::de::DeserializeOwned>(
+    response: &str,
+    what: &str,
+) -> Result<T, AIError> {
+    let trimmed = response.trim();
+    let json_str = if trimmed.starts_with("```") {
+        trimmed
+            .trim_start_matches("```json")
+            .trim_start_matches("```")
+            .trim_end_matches("```")
+            .trim()
+    } else {
+        trimmed
+    };
+
+    serde_json::from_str(json_str).map_err(|e| {
+        AIError::ParseError(format!("Invalid {}: {} - Response: {}", what, e, response))
+    })
 }
 
 /// Parsed AI command
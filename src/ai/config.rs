@@ -3,9 +3,12 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
-/// AI configuration for GLM API
+/// AI configuration, covering Gemini and any OpenAI-compatible or Ollama
+/// backend the `provider` field selects
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AIConfig {
+    #[serde(default = "default_provider")]
+    pub provider: String,
     pub api_key: String,
     #[serde(default = "default_model")]
     pub model: String,
@@ -13,6 +16,17 @@ pub struct AIConfig {
     pub base_url: String,
     #[serde(default = "default_enabled")]
     pub enabled: bool,
+    #[serde(default = "default_max_requests_per_second")]
+    pub max_requests_per_second: f64,
+    /// Path to write redacted request/response logs to, for debugging.
+    /// Logging is opt-in: unset unless explicitly configured or the
+    /// `VIMGRAM_AI_LOG` env var is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_path: Option<String>,
+}
+
+fn default_provider() -> String {
+    "gemini".to_string() // "gemini" | "openai" | "ollama" | "anthropic"
 }
 
 fn default_model() -> String {
@@ -27,13 +41,20 @@ fn default_enabled() -> bool {
     true
 }
 
+fn default_max_requests_per_second() -> f64 {
+    2.0
+}
+
 impl Default for AIConfig {
     fn default() -> Self {
         Self {
+            provider: default_provider(),
             api_key: String::new(),
             model: default_model(),
             base_url: default_base_url(),
             enabled: default_enabled(),
+            max_requests_per_second: default_max_requests_per_second(),
+            log_path: None,
         }
     }
 }
@@ -84,4 +105,20 @@ impl AIConfig {
     pub fn is_ready(&self) -> bool {
         self.enabled && !self.api_key.is_empty()
     }
+
+    /// Resolve where request/response logs should be written, if logging
+    /// is enabled at all: an explicit `log_path`, falling back to a default
+    /// path under the project data dir when `VIMGRAM_AI_LOG` is set
+    pub fn resolve_log_path(&self) -> Option<PathBuf> {
+        if let Some(path) = &self.log_path {
+            return Some(PathBuf::from(path));
+        }
+
+        if std::env::var("VIMGRAM_AI_LOG").is_ok() {
+            return ProjectDirs::from("", "", "vimgram")
+                .map(|p| p.data_dir().join("ai_requests.log"));
+        }
+
+        None
+    }
 }
@@ -0,0 +1,127 @@
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Log file is rotated once it grows past this size, keeping one previous
+/// generation around as `<name>.1`
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+/// One logged request/response pair, with credentials stripped
+#[derive(Debug, Serialize)]
+struct RequestLogEntry {
+    timestamp_secs: u64,
+    provider: String,
+    model: String,
+    endpoint: String,
+    latency_ms: u128,
+    prompt_tokens_estimate: usize,
+    response_tokens_estimate: usize,
+    error: Option<String>,
+}
+
+/// Opt-in, append-only logger for AI requests, gated by `AIConfig::log_path`.
+/// Records enough to diagnose a failure after the fact (model, endpoint,
+/// latency, rough token counts, error bodies) without ever writing the
+/// `api_key`/credentials used to make the call.
+pub struct Logger {
+    path: PathBuf,
+}
+
+impl Logger {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Record a completed request. Logging failures are swallowed (printed
+    /// to stderr) rather than surfaced as `AIError`, since a broken logger
+    /// shouldn't take down an otherwise-successful AI call.
+    #[allow(clippy::too_many_arguments)]
+    pub fn log_completion(
+        &self,
+        provider: &str,
+        model: &str,
+        endpoint: &str,
+        latency: Duration,
+        prompt: &str,
+        response: Result<&str, &str>,
+    ) {
+        let entry = RequestLogEntry {
+            timestamp_secs: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            provider: provider.to_string(),
+            model: model.to_string(),
+            endpoint: endpoint.to_string(),
+            latency_ms: latency.as_millis(),
+            prompt_tokens_estimate: estimate_tokens(prompt),
+            response_tokens_estimate: response.map(estimate_tokens).unwrap_or(0),
+            error: response.err().map(redact_api_key),
+        };
+
+        if let Err(e) = self.write(&entry) {
+            eprintln!("ai logger: failed to write log entry: {}", e);
+        }
+    }
+
+    fn write(&self, entry: &RequestLogEntry) -> std::io::Result<()> {
+        self.rotate_if_needed()?;
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let line = serde_json::to_string(entry)
+            .unwrap_or_else(|e| format!("{{\"error\":\"failed to serialize log entry: {}\"}}", e));
+        writeln!(file, "{}", line)
+    }
+
+    fn rotate_if_needed(&self) -> std::io::Result<()> {
+        let Ok(meta) = fs::metadata(&self.path) else {
+            return Ok(());
+        };
+        if meta.len() < MAX_LOG_BYTES {
+            return Ok(());
+        }
+
+        let rotated = rotated_path(&self.path);
+        fs::rename(&self.path, rotated)
+    }
+}
+
+fn rotated_path(path: &Path) -> PathBuf {
+    match path.extension() {
+        Some(ext) => path.with_extension(format!("{}.1", ext.to_string_lossy())),
+        None => path.with_extension("1"),
+    }
+}
+
+/// Rough token estimate (no tokenizer dependency): ~4 characters per token,
+/// good enough for spotting unexpectedly large requests in the log
+fn estimate_tokens(text: &str) -> usize {
+    (text.len() as f64 / 4.0).ceil() as usize
+}
+
+/// Mask the value of every `key=` query parameter in `text`: Gemini takes
+/// its API key that way, and reqwest's error text includes the request URL
+fn redact_api_key(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find("key=") {
+        out.push_str(&rest[..pos + 4]);
+        out.push_str("[REDACTED]");
+        rest = &rest[pos + 4..];
+        let end = rest
+            .find(|c: char| c == '&' || c == ')' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
@@ -0,0 +1,47 @@
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// Spaces out requests to at most `AIConfig::max_requests_per_second`, so a
+/// burst of `:ai` commands doesn't trip the backend's own rate limit.
+///
+/// This is a single-token bucket: each `acquire` call sleeps just long
+/// enough since the last acquire to respect the configured interval, then
+/// grants the token.
+pub struct RateLimiter {
+    interval: Duration,
+    last: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(max_requests_per_second: f64) -> Self {
+        let interval = if max_requests_per_second > 0.0 {
+            Duration::from_secs_f64(1.0 / max_requests_per_second)
+        } else {
+            Duration::ZERO
+        };
+
+        // Not `Instant::now() - interval`: for a very low configured rate,
+        // `interval` can exceed the process's monotonic-clock uptime and
+        // panic on underflow. Starting from `now` just means the first
+        // `acquire` waits one interval, same as any later one.
+        Self {
+            interval,
+            last: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Wait until a request is allowed to proceed
+    pub async fn acquire(&self) {
+        if self.interval.is_zero() {
+            return;
+        }
+
+        let mut last = self.last.lock().await;
+        let elapsed = last.elapsed();
+        if elapsed < self.interval {
+            tokio::time::sleep(self.interval - elapsed).await;
+        }
+        *last = Instant::now();
+    }
+}
@@ -1,9 +1,15 @@
 use grammers_client::{Client, Config, InitParams};
+use grammers_session::types::PeerRef;
 use grammers_session::Session;
+use grammers_tl_types as tl;
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 const SESSION_FILE: &str = ".bifrost_session";
 
+/// Maximum number of matching messages `search_messages` returns
+const SEARCH_RESULT_LIMIT: usize = 20;
+
 pub struct TelegramClient {
     pub client: Client,
 }
@@ -38,4 +44,143 @@ impl TelegramClient {
     pub async fn is_authorized(&self) -> Result<bool, Box<dyn std::error::Error>> {
         Ok(self.client.is_authorized().await?)
     }
+
+    /// Resolve a `@username` to a `PeerRef`, the capability type the rest of
+    /// the client API (sending, muting, iterating history, ...) expects
+    async fn resolve(&self, username: &str) -> Result<PeerRef, Box<dyn std::error::Error>> {
+        let peer = self
+            .client
+            .resolve_username(username.trim_start_matches('@'))
+            .await?
+            .ok_or_else(|| format!("no such user: {}", username))?;
+
+        peer.to_ref()
+            .await?
+            .ok_or_else(|| format!("no cached reference for: {}", username).into())
+    }
+
+    /// Mute a chat by pushing a `mute_until` notify setting `duration_seconds` in the future
+    pub async fn mute_chat(
+        &self,
+        username: &str,
+        duration_seconds: u32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let chat = self.resolve(username).await?;
+        let mute_until =
+            SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i32 + duration_seconds as i32;
+
+        self.client
+            .invoke(&tl::functions::account::UpdateNotifySettings {
+                peer: tl::enums::InputNotifyPeer::Peer(tl::types::InputNotifyPeer {
+                    peer: chat.into(),
+                }),
+                settings: tl::types::InputPeerNotifySettings {
+                    show_previews: Some(true),
+                    silent: Some(true),
+                    mute_until: Some(mute_until),
+                    sound: None,
+                    stories_muted: None,
+                    stories_hide_sender: None,
+                    stories_sound: None,
+                }
+                .into(),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Clear a chat's `mute_until` notify setting
+    pub async fn unmute_chat(&self, username: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let chat = self.resolve(username).await?;
+
+        self.client
+            .invoke(&tl::functions::account::UpdateNotifySettings {
+                peer: tl::enums::InputNotifyPeer::Peer(tl::types::InputNotifyPeer {
+                    peer: chat.into(),
+                }),
+                settings: tl::types::InputPeerNotifySettings {
+                    show_previews: Some(true),
+                    silent: Some(false),
+                    mute_until: Some(0),
+                    sound: None,
+                    stories_muted: None,
+                    stories_hide_sender: None,
+                    stories_sound: None,
+                }
+                .into(),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Search for messages matching `query` across dialogs, optionally
+    /// restricted to one sender's username
+    pub async fn search_messages(
+        &self,
+        query: &str,
+        from_user: Option<&str>,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let mut iter = self.client.search_all_messages().query(query);
+        let mut matches = Vec::new();
+
+        while let Some(message) = iter.next().await? {
+            if let Some(from_user) = from_user {
+                let sender_matches = message
+                    .sender()
+                    .and_then(|s| s.username().map(|u| u.eq_ignore_ascii_case(from_user.trim_start_matches('@'))))
+                    .unwrap_or(false);
+                if !sender_matches {
+                    continue;
+                }
+            }
+
+            matches.push(message.text().to_string());
+            if matches.len() >= SEARCH_RESULT_LIMIT {
+                break;
+            }
+        }
+
+        Ok(matches)
+    }
+
+    /// Send a text message to a user by username
+    pub async fn send_message(&self, username: &str, text: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let chat = self.resolve(username).await?;
+        self.client.send_message(chat, text).await?;
+        Ok(())
+    }
+
+    /// Fetch the most recent `limit` messages in a chat, oldest first, each
+    /// tagged with whether it was sent by us. Unlike `search_messages`
+    /// (a global, sender-filtered search), this is the real per-chat
+    /// history needed to build alternating conversation turns.
+    pub async fn chat_history(
+        &self,
+        username: &str,
+        limit: usize,
+    ) -> Result<Vec<HistoryMessage>, Box<dyn std::error::Error>> {
+        let chat = self.resolve(username).await?;
+        let mut iter = self.client.iter_messages(chat).limit(limit);
+        let mut messages = Vec::new();
+
+        while let Some(message) = iter.next().await? {
+            messages.push(HistoryMessage {
+                from_me: message.outgoing(),
+                text: message.text().to_string(),
+            });
+        }
+
+        // iter_messages yields newest-first; turns need chronological order
+        messages.reverse();
+        Ok(messages)
+    }
+}
+
+/// One message from `chat_history`, tagged with whether it was sent by us
+/// so the caller can build alternating `Role::User`/`Role::Model` turns
+pub struct HistoryMessage {
+    pub from_me: bool,
+    pub text: String,
 }
@@ -1,5 +1,6 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use crate::app::{App, Mode};
+use crate::ui::streaming;
 
 /// Handle keyboard input based on current mode
 pub fn handle_key(app: &mut App, key: KeyEvent) -> Option<String> {
@@ -59,12 +60,19 @@ fn handle_insert_mode(app: &mut App, key: KeyEvent) -> Option<String> {
             app.exit_insert();
         }
         
-        // Send message
+        // Send message, or kick off a streamed AI response for `:ai`/`:code`
         KeyCode::Enter => {
             if !app.input.is_empty() {
                 let message = app.input.clone();
                 app.input.clear();
-                return Some(message);
+
+                if let Some(prompt) = message.strip_prefix(":ai ") {
+                    streaming::start_ai_stream(app, None, prompt.to_string());
+                } else if let Some(prompt) = message.strip_prefix(":code ") {
+                    streaming::start_code_stream(app, prompt.to_string());
+                } else {
+                    return Some(message);
+                }
             }
         }
         
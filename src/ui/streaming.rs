@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use futures::StreamExt;
+use tokio::sync::mpsc;
+
+use crate::ai::client::{AIClient, AIError, CODE_ASSIST_SYSTEM};
+use crate::app::App;
+
+/// A piece of a streamed AI response, polled by the main loop and rendered
+/// into a panel as it arrives rather than all at once
+pub enum StreamEvent {
+    Delta(String),
+    Done,
+    Error(AIError),
+}
+
+/// Drive `AIClient::complete_stream` to completion in the background and
+/// forward each text delta over a channel the main loop can poll
+/// alongside terminal input, so a `:ai` reply or `code_assist` answer
+/// renders token-by-token instead of blocking until the full response
+/// returns.
+pub fn spawn_stream(
+    client: Arc<AIClient>,
+    system: Option<String>,
+    user: String,
+) -> mpsc::UnboundedReceiver<StreamEvent> {
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    tokio::spawn(async move {
+        let mut stream = match client.complete_stream(system.as_deref(), &user).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                let _ = tx.send(StreamEvent::Error(e));
+                return;
+            }
+        };
+
+        while let Some(chunk) = stream.next().await {
+            match chunk {
+                Ok(delta) => {
+                    if tx.send(StreamEvent::Delta(delta)).is_err() {
+                        return;
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(StreamEvent::Error(e));
+                    return;
+                }
+            }
+        }
+
+        let _ = tx.send(StreamEvent::Done);
+    });
+
+    rx
+}
+
+/// Kick off a streamed AI response for the `:ai <prompt>` / `:code <prompt>`
+/// input commands, replacing any stream already in flight. The result is
+/// polled out of `app.ai_stream_rx` by `poll_ai_stream` on each tick of the
+/// main loop.
+pub fn start_ai_stream(app: &mut App, system: Option<String>, prompt: String) {
+    let Some(client) = app.ai_client.clone() else {
+        app.ai_stream_buffer = AIError::NotConfigured.to_string();
+        return;
+    };
+
+    app.ai_stream_buffer.clear();
+    app.ai_stream_rx = Some(spawn_stream(client, system, prompt));
+}
+
+/// Start a `:code` streamed response using the shared code-assist system
+/// prompt, so it matches `AIClient::code_assist` exactly
+pub fn start_code_stream(app: &mut App, prompt: String) {
+    start_ai_stream(app, Some(CODE_ASSIST_SYSTEM.to_string()), prompt);
+}
+
+/// Drain whatever deltas have arrived since the last tick, appending them to
+/// `app.ai_stream_buffer`. Call this once per main-loop iteration; it never
+/// blocks. Returns once the stream is exhausted (`Done`) or failed (`Error`)
+/// by dropping `app.ai_stream_rx`, so a later call becomes a no-op until the
+/// next `start_ai_stream`.
+pub fn poll_ai_stream(app: &mut App) {
+    let Some(rx) = &mut app.ai_stream_rx else {
+        return;
+    };
+
+    loop {
+        match rx.try_recv() {
+            Ok(StreamEvent::Delta(delta)) => app.ai_stream_buffer.push_str(&delta),
+            Ok(StreamEvent::Done) => {
+                app.ai_stream_rx = None;
+                break;
+            }
+            Ok(StreamEvent::Error(e)) => {
+                app.ai_stream_buffer.push_str(&format!("\n[error: {}]", e));
+                app.ai_stream_rx = None;
+                break;
+            }
+            Err(mpsc::error::TryRecvError::Empty) => break,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                app.ai_stream_rx = None;
+                break;
+            }
+        }
+    }
+}